@@ -0,0 +1,35 @@
+use super::*;
+
+#[derive(Deserialize, Default, PartialEq, Debug)]
+pub struct Config {
+  #[serde(default)]
+  pub(crate) hidden: BTreeSet<InscriptionId>,
+  #[serde(default)]
+  pub(crate) chain: Option<Chain>,
+  #[serde(default)]
+  pub(crate) commit_interval: Option<u64>,
+  #[serde(default)]
+  pub(crate) cookie_file: Option<PathBuf>,
+  #[serde(default)]
+  pub(crate) data_dir: Option<PathBuf>,
+  #[serde(default)]
+  pub(crate) dogecoin_data_dir: Option<PathBuf>,
+  #[serde(default)]
+  pub(crate) dogecoin_rpc_password: Option<String>,
+  #[serde(default)]
+  pub(crate) dogecoin_rpc_username: Option<String>,
+  #[serde(default)]
+  pub(crate) first_inscription_height: Option<u64>,
+  #[serde(default)]
+  pub(crate) index_cache_size: Option<usize>,
+  #[serde(default)]
+  pub(crate) index_sats: bool,
+  #[serde(default)]
+  pub(crate) index_spent_sats: bool,
+  #[serde(default)]
+  pub(crate) index_transactions: bool,
+  #[serde(default)]
+  pub(crate) no_index_inscriptions: bool,
+  #[serde(default)]
+  pub(crate) rpc_url: Option<String>,
+}
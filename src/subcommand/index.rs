@@ -1,9 +1,13 @@
 use super::*;
 
 pub fn run(options: Options) -> Result {
-  let index = Index::open(&options)?;
+  options.validate_index_options()?;
 
-  index.update()?;
+  let index = Index::open_with_cache_size(&options, options.index_cache_size())?;
+
+  index.ensure_index_flags_match(options.index_flags())?;
+
+  index.update_with_commit_interval(options.commit_interval())?;
 
   Ok(())
 }